@@ -0,0 +1,193 @@
+//! Utilities for working with grapheme clusters (user-perceived "characters") rather than raw
+//! `char`s, so that cursors and selections never land inside a multi-codepoint cluster such as an
+//! emoji with skin-tone/ZWJ modifiers, a combining mark, or a CRLF line ending.
+use ropey::RopeSlice;
+use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete};
+
+/// Returns the char index of the next grapheme cluster boundary after `char_idx`, or
+/// `slice.len_chars()` if `char_idx` is already within the last cluster.
+#[must_use]
+pub fn next_grapheme_boundary(slice: RopeSlice, char_idx: usize) -> usize {
+    // Bounds check.
+    debug_assert!(char_idx <= slice.len_chars());
+
+    let byte_idx = slice.char_to_byte(char_idx);
+    let (mut chunk, mut chunk_byte_idx, _, _) = slice.chunk_at_byte(byte_idx);
+    let mut gc = GraphemeCursor::new(byte_idx, slice.len_bytes(), true);
+
+    loop {
+        match gc.next_boundary(chunk, chunk_byte_idx) {
+            Ok(None) => return slice.len_chars(),
+            Ok(Some(n)) => return slice.byte_to_char(n),
+            Err(GraphemeIncomplete::NextChunk) => {
+                chunk_byte_idx += chunk.len();
+                let (next_chunk, _, _, _) = slice.chunk_at_byte(chunk_byte_idx);
+                chunk = next_chunk;
+            }
+            Err(GraphemeIncomplete::PreContext(n)) => {
+                let (ctx_chunk, ctx_byte_idx, _, _) = slice.chunk_at_byte(n.saturating_sub(1));
+                gc.provide_context(ctx_chunk, ctx_byte_idx);
+            }
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+/// Returns the char index of the previous grapheme cluster boundary before `char_idx`, or `0` if
+/// `char_idx` is already within the first cluster.
+#[must_use]
+pub fn prev_grapheme_boundary(slice: RopeSlice, char_idx: usize) -> usize {
+    // Bounds check.
+    debug_assert!(char_idx <= slice.len_chars());
+
+    let byte_idx = slice.char_to_byte(char_idx);
+    let (mut chunk, mut chunk_byte_idx, _, _) = slice.chunk_at_byte(byte_idx);
+    let mut gc = GraphemeCursor::new(byte_idx, slice.len_bytes(), true);
+
+    loop {
+        match gc.prev_boundary(chunk, chunk_byte_idx) {
+            Ok(None) => return 0,
+            Ok(Some(n)) => return slice.byte_to_char(n),
+            Err(GraphemeIncomplete::PrevChunk) => {
+                let (prev_chunk, prev_chunk_byte_idx, _, _) =
+                    slice.chunk_at_byte(chunk_byte_idx.saturating_sub(1));
+                chunk = prev_chunk;
+                chunk_byte_idx = prev_chunk_byte_idx;
+            }
+            Err(GraphemeIncomplete::PreContext(n)) => {
+                let (ctx_chunk, ctx_byte_idx, _, _) = slice.chunk_at_byte(n.saturating_sub(1));
+                gc.provide_context(ctx_chunk, ctx_byte_idx);
+            }
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+/// `true` when `char_idx` already sits on a grapheme cluster boundary.
+fn is_grapheme_boundary(slice: RopeSlice, char_idx: usize) -> bool {
+    let byte_idx = slice.char_to_byte(char_idx);
+    let (chunk, chunk_byte_idx, _, _) = slice.chunk_at_byte(byte_idx);
+    let mut gc = GraphemeCursor::new(byte_idx, slice.len_bytes(), true);
+
+    loop {
+        match gc.is_boundary(chunk, chunk_byte_idx) {
+            Ok(is_boundary) => return is_boundary,
+            Err(GraphemeIncomplete::PreContext(n)) => {
+                let (ctx_chunk, ctx_byte_idx, _, _) = slice.chunk_at_byte(n.saturating_sub(1));
+                gc.provide_context(ctx_chunk, ctx_byte_idx);
+            }
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+/// If `char_idx` is in the middle of a grapheme cluster, round it forward to the next boundary;
+/// otherwise leave it untouched. Used to keep a moving/extending position from landing inside a
+/// cluster.
+#[must_use]
+pub fn ensure_grapheme_boundary_next(slice: RopeSlice, char_idx: usize) -> usize {
+    if char_idx == 0 || char_idx == slice.len_chars() || is_grapheme_boundary(slice, char_idx) {
+        char_idx
+    } else {
+        next_grapheme_boundary(slice, char_idx)
+    }
+}
+
+/// If `char_idx` is in the middle of a grapheme cluster, round it backward to the previous
+/// boundary; otherwise leave it untouched.
+#[must_use]
+pub fn ensure_grapheme_boundary_prev(slice: RopeSlice, char_idx: usize) -> usize {
+    if char_idx == 0 || char_idx == slice.len_chars() || is_grapheme_boundary(slice, char_idx) {
+        char_idx
+    } else {
+        prev_grapheme_boundary(slice, char_idx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ropey::Rope;
+
+    #[test]
+    fn test_boundaries_around_multi_codepoint_clusters() {
+        // "🇺🇸" is two regional-indicator scalars that together form a single grapheme
+        // cluster; the family emoji is four people joined by three ZWJs into one cluster.
+        let rope = Rope::from_str("a🇺🇸b👨‍👩‍👧‍👦c");
+        let slice = rope.slice(..);
+
+        let a_end = 1;
+        let flag_end = a_end + 2;
+        let b_end = flag_end + 1;
+        let family_end = b_end + 7;
+        let c_end = family_end + 1;
+
+        assert_eq!(next_grapheme_boundary(slice, 0), a_end);
+        assert_eq!(next_grapheme_boundary(slice, a_end), flag_end);
+        assert_eq!(next_grapheme_boundary(slice, flag_end), b_end);
+        assert_eq!(next_grapheme_boundary(slice, b_end), family_end);
+        assert_eq!(next_grapheme_boundary(slice, family_end), c_end);
+        assert_eq!(next_grapheme_boundary(slice, c_end), c_end);
+
+        assert_eq!(prev_grapheme_boundary(slice, c_end), family_end);
+        assert_eq!(prev_grapheme_boundary(slice, family_end), b_end);
+        assert_eq!(prev_grapheme_boundary(slice, b_end), flag_end);
+        assert_eq!(prev_grapheme_boundary(slice, flag_end), a_end);
+        assert_eq!(prev_grapheme_boundary(slice, a_end), 0);
+
+        // landing mid-cluster rounds to the nearest boundary in the requested direction, not
+        // the nearest char.
+        assert_eq!(next_grapheme_boundary(slice, a_end + 1), flag_end);
+        assert_eq!(prev_grapheme_boundary(slice, a_end + 1), a_end);
+        assert!(!is_grapheme_boundary(slice, a_end + 1));
+
+        assert_eq!(ensure_grapheme_boundary_next(slice, a_end + 1), flag_end);
+        assert_eq!(ensure_grapheme_boundary_prev(slice, a_end + 1), a_end);
+        // already on a boundary: both should be no-ops.
+        assert_eq!(ensure_grapheme_boundary_next(slice, flag_end), flag_end);
+        assert_eq!(ensure_grapheme_boundary_prev(slice, flag_end), flag_end);
+    }
+
+    #[test]
+    fn test_boundary_straddles_rope_chunk() {
+        // Build a rope large enough to span multiple internal chunks, then insert a
+        // two-codepoint cluster ("e" + combining acute accent, U+0301) exactly on a real
+        // chunk boundary found by walking the rope's own chunks — so GraphemeCursor is forced
+        // to pull pre-context from a neighboring chunk rather than staying within one.
+        let mut rope = Rope::from_str(&"x".repeat(8192));
+
+        let mut acc_bytes = 0;
+        let mut boundary_char = None;
+        for chunk in rope.chunks() {
+            acc_bytes += chunk.len();
+            if acc_bytes < rope.len_bytes() {
+                boundary_char = Some(rope.byte_to_char(acc_bytes));
+                break;
+            }
+        }
+        let boundary_char =
+            boundary_char.expect("an 8192-byte rope of 'x' should span more than one chunk");
+
+        rope.insert(boundary_char, "e\u{301}");
+
+        // confirm the cluster's two chars actually landed in different chunks; otherwise this
+        // test isn't exercising what it claims to.
+        let (_, e_chunk_start, _, _) = rope.chunk_at_char(boundary_char);
+        let (_, accent_chunk_start, _, _) = rope.chunk_at_char(boundary_char + 1);
+        assert_ne!(
+            e_chunk_start, accent_chunk_start,
+            "test setup didn't actually straddle a chunk boundary"
+        );
+
+        let slice = rope.slice(..);
+        assert_eq!(
+            next_grapheme_boundary(slice, boundary_char),
+            boundary_char + 2
+        );
+        assert_eq!(
+            prev_grapheme_boundary(slice, boundary_char + 2),
+            boundary_char
+        );
+        assert!(!is_grapheme_boundary(slice, boundary_char + 1));
+    }
+}
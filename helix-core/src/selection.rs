@@ -2,6 +2,9 @@
 //! single selection range.
 //!
 //! All positioning is done via `char` offsets into the buffer.
+use crate::graphemes::{
+    ensure_grapheme_boundary_next, ensure_grapheme_boundary_prev, next_grapheme_boundary,
+};
 use crate::{Assoc, ChangeSet, Rope, RopeSlice};
 use smallvec::{smallvec, SmallVec};
 use std::borrow::Cow;
@@ -15,7 +18,10 @@ fn abs_difference(x: usize, y: usize) -> usize {
     }
 }
 
-/// A single selection range. Anchor-inclusive, head-exclusive.
+/// A single selection range. `anchor` and `head` are gap positions: they sit *between* chars
+/// (position 1 is the gap between char 0 and char 1), not on top of them. A range therefore
+/// covers the half-open span `from()..to()`; a zero-width range (`from() == to()`) is a bare
+/// cursor, and a cursor "on" a char is represented as a 1-wide range.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Range {
     // TODO: optimize into u32
@@ -53,24 +59,22 @@ impl Range {
     /// Check two ranges for overlap.
     #[must_use]
     pub fn overlaps(&self, other: &Self) -> bool {
-        // cursor overlap is checked differently
+        // Two non-zero-width ranges that merely share an edge (one's `to()` is the
+        // other's `from()`) don't overlap under gap indexing: the shared gap belongs
+        // to neither range's char span. A zero-width range (a cursor sitting in a gap)
+        // is the exception: it still overlaps a range whose edge it sits on, so that
+        // e.g. a cursor at the end of a selection merges with it during normalization.
         if self.is_empty() {
-            self.from() <= other.to()
+            self.from() >= other.from() && self.from() <= other.to()
+        } else if other.is_empty() {
+            other.from() >= self.from() && other.from() <= self.to()
         } else {
-            self.from() < other.to()
+            self.from() < other.to() && other.from() < self.to()
         }
     }
 
     pub fn contains(&self, pos: usize) -> bool {
-        if self.is_empty() {
-            return false;
-        }
-
-        if self.anchor < self.head {
-            self.anchor <= pos && pos < self.head
-        } else {
-            self.head < pos && pos <= self.anchor
-        }
+        self.from() <= pos && pos < self.to()
     }
 
     /// Map a range through a set of changes. Returns a new range representing the same position
@@ -106,11 +110,66 @@ impl Range {
         }
     }
 
+    /// Merge this range with `other`, producing the smallest range that covers both, while
+    /// negotiating the result's direction: if both inputs are backward (`anchor > head`) the
+    /// merged range stays backward (`anchor` is the rightmost edge, `head` the leftmost);
+    /// otherwise the merged range is forward (`anchor` the leftmost edge, `head` the rightmost).
+    #[must_use]
+    pub fn merge(&self, other: Self) -> Self {
+        if self.anchor > self.head && other.anchor > other.head {
+            Range {
+                anchor: std::cmp::max(self.anchor, other.anchor),
+                head: std::cmp::min(self.head, other.head),
+            }
+        } else {
+            Range {
+                anchor: std::cmp::min(self.from(), other.from()),
+                head: std::cmp::max(self.to(), other.to()),
+            }
+        }
+    }
+
     // groupAt
 
+    /// Snap `anchor` and `head` onto grapheme cluster boundaries, so the range never starts or
+    /// ends in the middle of a multi-codepoint cluster. `head` rounds in its direction of travel
+    /// (forward ranges round it forward, backward ranges round it backward) and `anchor` rounds
+    /// the opposite way, so the range only ever grows to stay cluster-aligned, never shrinks.
+    #[must_use]
+    pub fn grapheme_aligned(&self, text: &RopeSlice) -> Range {
+        let (anchor, head) = if self.anchor <= self.head {
+            (
+                ensure_grapheme_boundary_prev(*text, self.anchor),
+                ensure_grapheme_boundary_next(*text, self.head),
+            )
+        } else {
+            (
+                ensure_grapheme_boundary_next(*text, self.anchor),
+                ensure_grapheme_boundary_prev(*text, self.head),
+            )
+        };
+        Range { anchor, head }
+    }
+
+    /// If the range is zero-width (a bare cursor), extend `head` forward to the next grapheme
+    /// boundary, so a cursor sitting on a boundary covers exactly one grapheme cluster. A cursor
+    /// at the end of the text has no grapheme after it and stays zero-width.
+    #[must_use]
+    pub fn min_width_1(&self, text: &RopeSlice) -> Range {
+        if self.is_empty() {
+            Range {
+                anchor: self.anchor,
+                head: next_grapheme_boundary(*text, self.head),
+            }
+        } else {
+            *self
+        }
+    }
+
     #[inline]
     pub fn fragment<'a>(&'a self, text: &'a RopeSlice) -> Cow<'a, str> {
-        Cow::from(text.slice(self.from()..self.to() + 1))
+        let range = self.min_width_1(text);
+        Cow::from(text.slice(range.from()..range.to()))
     }
 }
 
@@ -147,8 +206,37 @@ impl Selection {
         }
     }
 
-    // add_range // push
-    // replace_range
+    /// Append `range`, making it the new primary range, and re-normalize so the
+    /// sorted/merged invariant `new` guarantees still holds.
+    #[must_use]
+    pub fn push(mut self, range: Range) -> Self {
+        self.ranges.push(range);
+        let primary_index = self.ranges.len() - 1;
+        Self::new(self.ranges, primary_index)
+    }
+
+    /// Replace the range at `index` and re-normalize.
+    #[must_use]
+    pub fn replace(mut self, index: usize, range: Range) -> Self {
+        self.ranges[index] = range;
+        Self::new(self.ranges, self.primary_index)
+    }
+
+    /// Drop the range at `index`, adjusting `primary_index` to stay valid. A selection must
+    /// always hold at least one range, so removing the last remaining range is a no-op.
+    #[must_use]
+    pub fn remove(mut self, index: usize) -> Self {
+        if self.ranges.len() == 1 {
+            return self;
+        }
+
+        self.ranges.remove(index);
+        if index < self.primary_index {
+            self.primary_index -= 1;
+        }
+        self.primary_index = self.primary_index.min(self.ranges.len() - 1);
+        self
+    }
 
     /// Map selections over a set of changes. Useful for adjusting the selection position after
     /// applying changes to a document.
@@ -185,49 +273,7 @@ impl Selection {
     }
 
     #[must_use]
-    pub fn new(ranges: SmallVec<[Range; 1]>, primary_index: usize) -> Self {
-        fn normalize(mut ranges: SmallVec<[Range; 1]>, mut primary_index: usize) -> Selection {
-            let primary = ranges[primary_index];
-            ranges.sort_unstable_by_key(Range::from);
-            primary_index = ranges.iter().position(|&range| range == primary).unwrap();
-
-            let mut result: SmallVec<[Range; 1]> = SmallVec::new();
-
-            // TODO: we could do with one vec by removing elements as we mutate
-
-            for (i, range) in ranges.into_iter().enumerate() {
-                // if previous value exists
-                if let Some(prev) = result.last_mut() {
-                    // and we overlap it
-                    if range.overlaps(prev) {
-                        let from = prev.from();
-                        let to = std::cmp::max(range.to(), prev.to());
-
-                        if i <= primary_index {
-                            primary_index -= 1
-                        }
-
-                        // merge into previous
-                        if range.anchor > range.head {
-                            prev.anchor = to;
-                            prev.head = from;
-                        } else {
-                            prev.anchor = from;
-                            prev.head = to;
-                        }
-                        continue;
-                    }
-                }
-
-                result.push(range)
-            }
-
-            Selection {
-                ranges: result,
-                primary_index,
-            }
-        }
-
+    pub fn new(mut ranges: SmallVec<[Range; 1]>, mut primary_index: usize) -> Self {
         // fast path for a single selection (cursor)
         if ranges.len() == 1 {
             return Selection {
@@ -236,8 +282,54 @@ impl Selection {
             };
         }
 
-        // TODO: only normalize if needed (any ranges out of order)
-        normalize(ranges, primary_index)
+        // fast path: multi-cursor selections are built up one movement at a time and are
+        // already sorted and non-overlapping far more often than not. Skip the sort/merge
+        // passes entirely in that case.
+        let already_normalized = ranges
+            .windows(2)
+            .all(|w| w[0].from() <= w[1].from() && !w[1].overlaps(&w[0]));
+        if already_normalized {
+            return Selection {
+                ranges,
+                primary_index,
+            };
+        }
+
+        // Pull the primary range out before sorting the rest, then reinsert it at its sorted
+        // position. This pins down the primary's new index directly instead of re-finding it
+        // afterwards with an equality scan, which is ambiguous whenever multiple ranges share
+        // the same anchor/head.
+        let primary = ranges.remove(primary_index);
+        ranges.sort_unstable_by_key(Range::from);
+        primary_index = ranges.partition_point(|range| range.from() <= primary.from());
+        ranges.insert(primary_index, primary);
+
+        // Merge overlapping ranges in place with a write-cursor: `write` is the last range
+        // committed to the output so far, `read` scans forward over the rest of the (already
+        // sorted) buffer. A run of mutually overlapping ranges collapses onto a single `write`
+        // slot, so the whole pass reuses `ranges`' own buffer instead of allocating a second one
+        // to build the result into.
+        let mut write = 0;
+        for read in 1..ranges.len() {
+            let range = ranges[read];
+
+            if range.overlaps(&ranges[write]) {
+                ranges[write] = ranges[write].merge(range);
+            } else {
+                write += 1;
+                ranges[write] = range;
+            }
+
+            if primary_index == read {
+                primary_index = write;
+            }
+        }
+        ranges.truncate(write + 1);
+
+        Selection {
+            ranges,
+            primary_index,
+        }
     }
 
     /// Takes a closure and maps each selection over the closure.
@@ -254,44 +346,212 @@ impl Selection {
     pub fn fragments<'a>(&'a self, text: &'a RopeSlice) -> impl Iterator<Item = Cow<str>> + 'a {
         self.ranges.iter().map(move |range| range.fragment(text))
     }
+
+    /// Keep only ranges whose `fragment` matches `regex`. `None` if that would leave the
+    /// selection empty.
+    #[must_use]
+    pub fn keep_matches(&self, text: &RopeSlice, regex: &crate::regex::Regex) -> Option<Selection> {
+        self.filter_matches(text, regex, true)
+    }
+
+    /// Discard ranges whose `fragment` matches `regex` — the complement of
+    /// [`Self::keep_matches`]. `None` if that would leave the selection empty.
+    #[must_use]
+    pub fn remove_matches(
+        &self,
+        text: &RopeSlice,
+        regex: &crate::regex::Regex,
+    ) -> Option<Selection> {
+        self.filter_matches(text, regex, false)
+    }
+
+    fn filter_matches(
+        &self,
+        text: &RopeSlice,
+        regex: &crate::regex::Regex,
+        keep: bool,
+    ) -> Option<Selection> {
+        let primary = self.primary();
+        let mut result: SmallVec<[Range; 1]> = SmallVec::new();
+        let mut primary_index = 0;
+
+        for &range in self.ranges.iter() {
+            if regex.is_match(&range.fragment(text)) == keep {
+                if range == primary {
+                    primary_index = result.len();
+                }
+                result.push(range);
+            }
+        }
+
+        if result.is_empty() {
+            None
+        } else {
+            Some(Self::new(result, primary_index))
+        }
+    }
 }
 
 // TODO: checkSelection -> check if valid for doc length
 
-// TODO: support to split on capture #N instead of whole match
+/// Push a sub-range produced by splitting a source range, honoring that source range's direction
+/// and tracking which sub-range the overall primary head lands in. A `from == to` span is an
+/// empty match, or the zero-width gap left between two adjacent matches — drop it rather than
+/// emitting it as a spurious cursor, *unless* `force` is set: a source range with no internal
+/// splits at all (no matches found in it) must always be carried through as-is, including when
+/// it was a bare zero-width cursor to begin with.
+fn push_split_range(
+    result: &mut SmallVec<[Range; 1]>,
+    primary_index: &mut Option<usize>,
+    primary_head: usize,
+    backward: bool,
+    from: usize,
+    to: usize,
+    force: bool,
+) {
+    if from == to && !force {
+        return;
+    }
+
+    let range = if backward {
+        Range::new(to, from)
+    } else {
+        Range::new(from, to)
+    };
+
+    if primary_index.is_none() && range.from() <= primary_head && primary_head <= range.to() {
+        *primary_index = Some(result.len());
+    }
+
+    result.push(range);
+}
+
+/// Shared bookkeeping for `split_on_matches`/`split_on_capture`: walks `spans` (delimiter byte
+/// ranges within `sel`'s fragment, in fragment-relative byte offsets), converts each to char
+/// offsets and emits the sub-ranges between them. If `spans` is empty, `sel` had no internal
+/// matches at all and is carried through unsplit — this is what keeps a bare input cursor (or
+/// any other unmatched range) from being silently dropped. Likewise, if the match(es) consume
+/// `sel` entirely and leave no surviving sub-range, `sel` collapses to a zero-width cursor at its
+/// start rather than vanishing outright.
+fn split_sel_on_spans(
+    text: &RopeSlice,
+    sel: &Range,
+    spans: impl Iterator<Item = (usize, usize)>,
+    result: &mut SmallVec<[Range; 1]>,
+    primary_index: &mut Option<usize>,
+    primary_head: usize,
+) {
+    let backward = sel.anchor > sel.head;
+    let sel_start = sel.from();
+    let sel_end = sel.to();
+
+    let start_byte = text.char_to_byte(sel_start);
+    let result_len_before = result.len();
+
+    let mut start = sel_start;
+    let mut matched = false;
+
+    for (span_start, span_end) in spans {
+        matched = true;
+
+        let end = text.byte_to_char(start_byte + span_start);
+        push_split_range(
+            result,
+            primary_index,
+            primary_head,
+            backward,
+            start,
+            end,
+            false,
+        );
+        start = text.byte_to_char(start_byte + span_end);
+    }
+
+    if start <= sel_end {
+        push_split_range(
+            result,
+            primary_index,
+            primary_head,
+            backward,
+            start,
+            sel_end,
+            !matched,
+        );
+    }
+
+    if result.len() == result_len_before {
+        push_split_range(
+            result,
+            primary_index,
+            primary_head,
+            backward,
+            sel_start,
+            sel_start,
+            true,
+        );
+    }
+}
+
 pub fn split_on_matches(
     text: &RopeSlice,
     selections: &Selection,
     regex: &crate::regex::Regex,
 ) -> Selection {
+    let primary_head = selections.primary().head;
     let mut result = SmallVec::with_capacity(selections.ranges().len());
+    let mut primary_index = None;
 
     for sel in selections.ranges() {
         // TODO: can't avoid occasional allocations since Regex can't operate on chunks yet
-        let fragment = sel.fragment(&text);
-
-        let mut sel_start = sel.from();
-        let sel_end = sel.to();
-
-        let mut start_byte = text.char_to_byte(sel_start);
-
-        let mut start = sel_start;
+        let fragment = sel.fragment(text);
+        let spans = regex
+            .find_iter(&fragment)
+            .map(|mat| (mat.start(), mat.end()));
+
+        split_sel_on_spans(
+            text,
+            sel,
+            spans,
+            &mut result,
+            &mut primary_index,
+            primary_head,
+        );
+    }
 
-        for mat in regex.find_iter(&fragment) {
-            // TODO: retain range direction
+    Selection::new(result, primary_index.unwrap_or(0))
+}
 
-            let end = text.byte_to_char(start_byte + mat.start());
-            result.push(Range::new(start, end - 1));
-            start = text.byte_to_char(start_byte + mat.end());
-        }
+/// Like [`split_on_matches`], but splits on the span of a specific capture group rather than the
+/// whole match — e.g. splitting CSV on just the comma capture keeps the surrounding fields
+/// together even when the pattern that finds the comma also matches leading/trailing context.
+/// Matches where the capture group didn't participate are skipped.
+pub fn split_on_capture(
+    text: &RopeSlice,
+    selections: &Selection,
+    regex: &crate::regex::Regex,
+    capture_index: usize,
+) -> Selection {
+    let primary_head = selections.primary().head;
+    let mut result = SmallVec::with_capacity(selections.ranges().len());
+    let mut primary_index = None;
 
-        if start <= sel_end {
-            result.push(Range::new(start, sel_end));
-        }
+    for sel in selections.ranges() {
+        let fragment = sel.fragment(text);
+        let spans = regex
+            .captures_iter(&fragment)
+            .filter_map(|caps| caps.get(capture_index).map(|mat| (mat.start(), mat.end())));
+
+        split_sel_on_spans(
+            text,
+            sel,
+            spans,
+            &mut result,
+            &mut primary_index,
+            primary_head,
+        );
     }
 
-    // TODO: figure out a new primary index
-    Selection::new(result, 0)
+    Selection::new(result, primary_index.unwrap_or(0))
 }
 
 #[cfg(test)]
@@ -358,9 +618,34 @@ mod test {
         assert_eq!(range.contains(13), false);
 
         let range = Range::new(9, 6);
-        assert_eq!(range.contains(9), true);
+        assert_eq!(range.contains(9), false);
         assert_eq!(range.contains(7), true);
-        assert_eq!(range.contains(6), false);
+        assert_eq!(range.contains(6), true);
+        assert_eq!(range.contains(5), false);
+    }
+
+    #[test]
+    fn test_grapheme_aligned_and_min_width_1_on_clusters() {
+        // family emoji: 👨 ZWJ 👩 ZWJ 👧 ZWJ 👦 — 7 chars, one grapheme cluster, flanked by
+        // plain ASCII so the cluster's boundaries (1 and 8) are unambiguous.
+        let text = Rope::from("a👨‍👩‍👧‍👦b");
+        let slice = text.slice(..);
+
+        // landing inside the cluster widens outward to its boundaries, never shrinks.
+        assert_eq!(Range::new(4, 4).grapheme_aligned(&slice), Range::new(1, 8));
+        // a backward range with its head mid-cluster widens the head backward to the
+        // cluster's start, same as the forward case above.
+        assert_eq!(Range::new(8, 4).grapheme_aligned(&slice), Range::new(8, 1));
+
+        // a zero-width cursor sitting on the cluster's start boundary widens forward by the
+        // whole cluster, not by one char.
+        let cursor = Range::new(1, 1).min_width_1(&slice);
+        assert_eq!(cursor, Range::new(1, 8));
+        assert_eq!(cursor.fragment(&slice), "👨‍👩‍👧‍👦");
+
+        // a non-empty range is left untouched by min_width_1 even if it already covers the
+        // cluster exactly.
+        assert_eq!(Range::new(1, 8).min_width_1(&slice), Range::new(1, 8));
     }
 
     #[test]
@@ -373,20 +658,195 @@ mod test {
 
         let result = split_on_matches(&text.slice(..), &selections, &Regex::new(r"\s+").unwrap());
 
+        // the trailing zero-width gap left after the final match (at 19) is dropped rather
+        // than emitted as a spurious cursor.
         assert_eq!(
             result.ranges(),
             &[
-                Range::new(0, 3),
-                Range::new(5, 7),
-                Range::new(10, 11),
-                Range::new(15, 17),
-                Range::new(19, 19),
+                Range::new(0, 4),
+                Range::new(5, 8),
+                Range::new(10, 12),
+                Range::new(15, 18),
             ]
         );
 
         assert_eq!(
             result.fragments(&text.slice(..)).collect::<Vec<_>>(),
-            &["abcd", "efg", "rs", "xyz", "1"]
+            &["abcd", "efg", "rs", "xyz"]
+        );
+
+        // primary head (8) fell inside the source primary range (0, 8), which is now split
+        // into (0, 4) and (5, 8); the new primary should follow it to (5, 8).
+        assert_eq!(result.primary(), Range::new(5, 8));
+
+        // a backward source range should produce backward sub-ranges.
+        let backward = Selection::single(8, 0);
+        let result = split_on_matches(&text.slice(..), &backward, &Regex::new(r"\s+").unwrap());
+        assert_eq!(result.ranges(), &[Range::new(4, 0), Range::new(8, 5)]);
+
+        // a bare cursor with no match inside it at all is carried through as-is rather than
+        // being dropped — it never enters the split loop, so it must not be treated as an
+        // internal zero-width gap.
+        let cursor = Selection::point(3);
+        let result = split_on_matches(&text.slice(..), &cursor, &Regex::new("XYZ").unwrap());
+        assert_eq!(result.ranges(), &[Range::new(3, 3)]);
+
+        // a match that consumes the entire input range leaves no leading or trailing
+        // sub-range at all; the range must collapse to a cursor rather than vanish.
+        let text = Rope::from("a b");
+        let whitespace = Selection::single(1, 2);
+        let result = split_on_matches(&text.slice(..), &whitespace, &Regex::new(r"\s+").unwrap());
+        assert_eq!(result.ranges(), &[Range::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_split_on_capture() {
+        use crate::regex::Regex;
+
+        let text = Rope::from("alpha,beta,,gamma");
+        let selections = Selection::single(0, text.len_chars());
+
+        let result = split_on_capture(
+            &text.slice(..),
+            &selections,
+            &Regex::new(r"[^,]*(,)").unwrap(),
+            1,
+        );
+
+        // the empty field between the two commas produces no zero-width sub-range.
+        assert_eq!(
+            result.fragments(&text.slice(..)).collect::<Vec<_>>(),
+            &["alpha", "beta", "gamma"]
+        );
+
+        // a capture that consumes the entire input range leaves no sub-range at all; the
+        // range must collapse to a cursor rather than vanish.
+        let text = Rope::from(",");
+        let whole = Selection::single(0, 1);
+        let result = split_on_capture(&text.slice(..), &whole, &Regex::new("(,)").unwrap(), 1);
+        assert_eq!(result.ranges(), &[Range::new(0, 0)]);
+    }
+
+    #[test]
+    fn test_push_merges_and_tracks_primary() {
+        let selection = Selection::new(smallvec![Range::new(0, 2), Range::new(10, 12)], 0);
+
+        // pushing always makes the new range primary; here it overlaps the second range, so
+        // the merged range must stay primary rather than reverting to the first one.
+        let selection = selection.push(Range::new(11, 14));
+
+        assert_eq!(selection.ranges(), &[Range::new(0, 2), Range::new(10, 14)]);
+        assert_eq!(selection.primary(), Range::new(10, 14));
+    }
+
+    #[test]
+    fn test_replace_renormalizes() {
+        let selection = Selection::new(smallvec![Range::new(0, 2), Range::new(10, 12)], 1);
+
+        // replacing a range can make it overlap its neighbor; the result must still come out
+        // merged and sorted like any other re-normalization.
+        let selection = selection.replace(1, Range::new(1, 12));
+
+        assert_eq!(selection.ranges(), &[Range::new(0, 12)]);
+        assert_eq!(selection.primary(), Range::new(0, 12));
+    }
+
+    #[test]
+    fn test_remove_of_primary_range() {
+        let selection = Selection::new(
+            smallvec![Range::new(0, 2), Range::new(5, 7), Range::new(10, 12)],
+            1,
+        );
+
+        // removing the primary range itself must fall back to a valid neighbor rather than
+        // panicking or leaving primary_index pointing past the end.
+        let selection = selection.remove(1);
+
+        assert_eq!(selection.ranges(), &[Range::new(0, 2), Range::new(10, 12)]);
+        assert_eq!(selection.primary(), Range::new(10, 12));
+    }
+
+    #[test]
+    fn test_keep_matches_tracks_surviving_primary() {
+        use crate::regex::Regex;
+
+        let text = Rope::from("foo bar foo");
+        // primary is the trailing "foo"; keep_matches drops the middle "bar" range, so the
+        // primary must still follow "foo" afterward instead of resetting to the first survivor.
+        let selection = Selection::new(
+            smallvec![Range::new(0, 3), Range::new(4, 7), Range::new(8, 11)],
+            2,
         );
+
+        let result = selection
+            .keep_matches(&text.slice(..), &Regex::new("foo").unwrap())
+            .unwrap();
+
+        assert_eq!(result.ranges(), &[Range::new(0, 3), Range::new(8, 11)]);
+        assert_eq!(result.primary(), Range::new(8, 11));
+    }
+
+    #[test]
+    fn test_remove_matches_tracks_surviving_primary() {
+        use crate::regex::Regex;
+
+        let text = Rope::from("foo bar foo");
+        // primary is "bar"; remove_matches drops every "foo" range but keeps "bar", so the
+        // primary should still follow "bar" rather than resetting to the first survivor.
+        let selection = Selection::new(
+            smallvec![Range::new(0, 3), Range::new(4, 7), Range::new(8, 11)],
+            1,
+        );
+
+        let result = selection
+            .remove_matches(&text.slice(..), &Regex::new("foo").unwrap())
+            .unwrap();
+
+        assert_eq!(result.ranges(), &[Range::new(4, 7)]);
+        assert_eq!(result.primary(), Range::new(4, 7));
+    }
+
+    #[test]
+    fn bench_normalize_large_sorted_selection_takes_fast_path() {
+        // Already-sorted, non-overlapping ranges — the common case for a multi-cursor
+        // selection built up by repeated movement — must go straight through, with no sort,
+        // no merge pass, and no second buffer.
+        let n = 10_000;
+        let ranges: SmallVec<[Range; 1]> = (0..n).map(|i| Range::new(i * 3, i * 3 + 1)).collect();
+
+        let selection = Selection::new(ranges, n / 2);
+
+        assert_eq!(selection.ranges().len(), n);
+        assert_eq!(
+            selection.primary(),
+            Range::new((n / 2) * 3, (n / 2) * 3 + 1)
+        );
+    }
+
+    #[test]
+    fn bench_normalize_large_unsorted_selection() {
+        // 10k non-overlapping ranges submitted in reverse order force the general sort +
+        // in-place merge path at a size representative of a large multi-cursor edit.
+        let n = 10_000;
+        let ranges: SmallVec<[Range; 1]> = (0..n)
+            .map(|i| {
+                let start = (n - 1 - i) * 3;
+                Range::new(start, start + 1)
+            })
+            .collect();
+
+        // submitted near the end of the descending input; after sorting it should land
+        // second from the start.
+        let primary_value = Range::new(3, 4);
+        let primary_index = ranges.iter().position(|&r| r == primary_value).unwrap();
+
+        let selection = Selection::new(ranges, primary_index);
+
+        assert_eq!(selection.ranges().len(), n);
+        assert!(selection
+            .ranges()
+            .windows(2)
+            .all(|w| w[0].to() <= w[1].from()));
+        assert_eq!(selection.primary(), primary_value);
     }
 }